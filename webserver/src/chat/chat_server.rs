@@ -0,0 +1,18 @@
+use actix::{Actor, Context};
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+
+/// Actor coordinating chat rooms and connected sessions.
+pub struct ChatServer {
+    visitor_count: Arc<AtomicUsize>,
+}
+
+impl ChatServer {
+    pub fn new(visitor_count: Arc<AtomicUsize>) -> Self {
+        ChatServer { visitor_count }
+    }
+}
+
+impl Actor for ChatServer {
+    type Context = Context<Self>;
+}
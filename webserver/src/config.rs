@@ -0,0 +1,37 @@
+use std::env;
+
+use crate::services::password_service::HashBackend;
+
+/// Process-wide configuration loaded once at startup from the environment.
+#[derive(Clone)]
+pub struct Config {
+    pub database_url: String,
+    pub secret: String,
+    pub jwt_ttl_seconds: i64,
+    pub hash_backend: HashBackend,
+    pub hash_cost: u32,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        Config {
+            database_url: env::var("DATABASE_URL").expect("DATABASE_URL must be set"),
+            secret: env::var("JWT_SECRET").expect("JWT_SECRET must be set"),
+            jwt_ttl_seconds: env::var("JWT_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(24 * 60 * 60),
+            hash_backend: env::var("HASH_BACKEND")
+                .ok()
+                .map(|value| match value.to_lowercase().as_str() {
+                    "bcrypt" => HashBackend::Bcrypt,
+                    _ => HashBackend::Argon2,
+                })
+                .unwrap_or(HashBackend::Argon2),
+            hash_cost: env::var("HASH_COST")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(bcrypt::DEFAULT_COST),
+        }
+    }
+}
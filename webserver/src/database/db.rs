@@ -0,0 +1,22 @@
+use diesel::pg::PgConnection;
+use diesel::r2d2::{self, ConnectionManager};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+pub type Pool = r2d2::Pool<ConnectionManager<PgConnection>>;
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+pub fn establish_connection(database_url: &str) -> Pool {
+    let manager = ConnectionManager::<PgConnection>::new(database_url);
+    r2d2::Pool::builder()
+        .build(manager)
+        .expect("Failed to create database pool")
+}
+
+pub fn run_migrations(
+    conn: &mut PgConnection,
+    migrations: EmbeddedMigrations,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    conn.run_pending_migrations(migrations)?;
+    Ok(())
+}
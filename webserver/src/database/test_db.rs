@@ -0,0 +1,67 @@
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
+use diesel::{Connection, RunQueryDsl};
+use diesel_migrations::MigrationHarness;
+use std::env;
+
+use super::db::MIGRATIONS;
+
+/// A throwaway Postgres database created for a single test and dropped when it goes out of scope.
+pub struct TestDb {
+    pool: Pool<ConnectionManager<PgConnection>>,
+    db_name: String,
+    admin_url: String,
+}
+
+impl TestDb {
+    pub fn new() -> Self {
+        let base_url =
+            env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set for tests");
+        let db_name = format!("test_db_{}", uuid::Uuid::new_v4().simple());
+
+        let mut admin_conn = PgConnection::establish(&base_url)
+            .unwrap_or_else(|_| panic!("Failed to connect to {}", base_url));
+        diesel::sql_query(format!("CREATE DATABASE {}", db_name))
+            .execute(&mut admin_conn)
+            .expect("Failed to create test database");
+
+        let db_url = format!("{}/{}", base_url, db_name);
+        let manager = ConnectionManager::<PgConnection>::new(&db_url);
+        let pool = Pool::builder()
+            .build(manager)
+            .expect("Failed to create test database pool");
+
+        run_migrations(&mut pool.get().expect("Failed to get test connection"));
+
+        TestDb {
+            pool,
+            db_name,
+            admin_url: base_url,
+        }
+    }
+
+    pub fn conn(&self) -> PooledConnection<ConnectionManager<PgConnection>> {
+        self.pool.get().expect("Failed to get test connection")
+    }
+
+    pub fn pool(&self) -> Pool<ConnectionManager<PgConnection>> {
+        self.pool.clone()
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        if let Ok(mut admin_conn) = PgConnection::establish(&self.admin_url) {
+            let _ = diesel::sql_query(format!(
+                "DROP DATABASE IF EXISTS {} WITH (FORCE)",
+                self.db_name
+            ))
+            .execute(&mut admin_conn);
+        }
+    }
+}
+
+pub fn run_migrations(conn: &mut PgConnection) {
+    conn.run_pending_migrations(MIGRATIONS)
+        .expect("Failed to run migrations on test database");
+}
@@ -0,0 +1,64 @@
+use actix_web::{HttpResponse, ResponseError};
+use diesel::result::{DatabaseErrorInformation, DatabaseErrorKind, Error as DieselError};
+use thiserror::Error;
+
+/// Domain-level errors surfaced by the services layer, distinct from `diesel::result::Error`.
+#[derive(Error, Debug)]
+pub enum ServiceError {
+    #[error("username is already taken")]
+    UsernameTaken,
+
+    #[error("email is already registered")]
+    EmailTaken,
+
+    #[error("user not found")]
+    UserNotFound,
+
+    #[error("invalid credentials")]
+    InvalidCredentials,
+
+    #[error("failed to hash password")]
+    PasswordHashError,
+
+    #[error("failed to issue or verify token")]
+    TokenError,
+
+    #[error("database error: {0}")]
+    DatabaseError(DieselError),
+}
+
+impl ResponseError for ServiceError {
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            ServiceError::UsernameTaken => HttpResponse::Conflict().json(self.to_string()),
+            ServiceError::EmailTaken => HttpResponse::Conflict().json(self.to_string()),
+            ServiceError::UserNotFound => HttpResponse::NotFound().json(self.to_string()),
+            ServiceError::InvalidCredentials => {
+                HttpResponse::Unauthorized().json(self.to_string())
+            }
+            ServiceError::PasswordHashError => {
+                HttpResponse::InternalServerError().json(self.to_string())
+            }
+            ServiceError::TokenError => HttpResponse::InternalServerError().json(self.to_string()),
+            ServiceError::DatabaseError(error) => {
+                log::error!("Database error: {:?}", error);
+                HttpResponse::InternalServerError().json("internal server error")
+            }
+        }
+    }
+}
+
+impl From<DieselError> for ServiceError {
+    fn from(error: DieselError) -> Self {
+        match error {
+            DieselError::NotFound => ServiceError::UserNotFound,
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, ref info) => {
+                match info.constraint_name() {
+                    Some("users_email_key") => ServiceError::EmailTaken,
+                    _ => ServiceError::UsernameTaken,
+                }
+            }
+            other => ServiceError::DatabaseError(other),
+        }
+    }
+}
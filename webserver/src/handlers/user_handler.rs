@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use actix_web::{web, HttpResponse};
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::PgConnection;
+use serde::Deserialize;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::config::Config;
+use crate::errors::ServiceError;
+use crate::middleware::admin_guard::AdminUser;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::services::user_service;
+
+#[derive(Deserialize, Validate)]
+pub struct RegisterRequest {
+    #[validate(length(min = 3, max = 32), custom = "validate_username_charset")]
+    pub username: String,
+    #[validate(length(min = 8))]
+    pub password: String,
+    #[validate(must_match(other = "password"))]
+    pub confirm_password: String,
+    #[validate(email)]
+    pub email: Option<String>,
+}
+
+/// Trims and lowercases an email so equivalent addresses validate and store identically.
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn validate_username_charset(username: &str) -> Result<(), ValidationError> {
+    if username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("invalid_username_charset");
+        error.message = Some("username may only contain letters, digits and underscores".into());
+        Err(error)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    pub username_or_email: String,
+    pub password: String,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    #[validate(length(min = 8))]
+    pub new_password: String,
+}
+
+/// Renders `validator::ValidationErrors` as a flat `field -> message` JSON map.
+fn validation_error_response(errors: &ValidationErrors) -> HttpResponse {
+    let fields: BTreeMap<&str, String> = errors
+        .field_errors()
+        .iter()
+        .map(|(field, field_errors)| {
+            let message = field_errors
+                .first()
+                .map(|error| {
+                    error
+                        .message
+                        .clone()
+                        .unwrap_or_else(|| error.code.clone())
+                        .to_string()
+                })
+                .unwrap_or_default();
+            (*field, message)
+        })
+        .collect();
+    HttpResponse::BadRequest().json(fields)
+}
+
+pub async fn register(
+    pool: web::Data<Pool<ConnectionManager<PgConnection>>>,
+    config: web::Data<Config>,
+    mut payload: web::Json<RegisterRequest>,
+) -> Result<HttpResponse, ServiceError> {
+    payload.email = payload.email.take().map(|email| normalize_email(&email));
+
+    if let Err(errors) = payload.validate() {
+        return Ok(validation_error_response(&errors));
+    }
+
+    let mut conn = pool.get().expect("Unable to get db connection");
+
+    let user = user_service::register_user(
+        &mut conn,
+        &payload.username,
+        &payload.password,
+        payload.email.as_deref(),
+        config.hash_backend,
+        config.hash_cost,
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(user))
+}
+
+pub async fn login(
+    pool: web::Data<Pool<ConnectionManager<PgConnection>>>,
+    config: web::Data<Config>,
+    payload: web::Json<LoginRequest>,
+) -> Result<HttpResponse, ServiceError> {
+    let mut conn = pool.get().expect("Unable to get db connection");
+
+    // Only normalize values that look like an email; usernames are stored as typed.
+    let username_or_email = if payload.username_or_email.contains('@') {
+        normalize_email(&payload.username_or_email)
+    } else {
+        payload.username_or_email.trim().to_string()
+    };
+
+    let user_with_token = user_service::login(
+        &mut conn,
+        &username_or_email,
+        &payload.password,
+        &config.secret,
+        config.jwt_ttl_seconds,
+        config.hash_backend,
+        config.hash_cost,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(user_with_token))
+}
+
+pub async fn list_users(
+    pool: web::Data<Pool<ConnectionManager<PgConnection>>>,
+    _admin: AdminUser,
+) -> Result<HttpResponse, ServiceError> {
+    let mut conn = pool.get().expect("Unable to get db connection");
+
+    let users = user_service::list_users(&mut conn).await?;
+    Ok(HttpResponse::Ok().json(users))
+}
+
+pub async fn change_password(
+    pool: web::Data<Pool<ConnectionManager<PgConnection>>>,
+    config: web::Data<Config>,
+    auth_user: AuthenticatedUser,
+    payload: web::Json<ChangePasswordRequest>,
+) -> Result<HttpResponse, ServiceError> {
+    if let Err(errors) = payload.validate() {
+        return Ok(validation_error_response(&errors));
+    }
+
+    let mut conn = pool.get().expect("Unable to get db connection");
+
+    user_service::change_password(
+        &mut conn,
+        auth_user.id,
+        &payload.current_password,
+        &payload.new_password,
+        config.hash_backend,
+        config.hash_cost,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,12 @@
+extern crate diesel;
+
+pub mod chat;
+pub mod config;
+pub mod database;
+pub mod errors;
+pub mod handlers;
+pub mod middleware;
+pub mod models;
+pub mod routes;
+pub mod schema;
+pub mod services;
@@ -1,33 +1,24 @@
-use crate::database::db;
 use actix::Actor;
 use actix_cors::Cors;
 use actix_web::middleware::Logger;
 use actix_web::web::{self, Data};
 use actix_web::{App, HttpServer};
-use chat::chat_server;
-use config::Config;
-use database::db::MIGRATIONS;
 use dotenv::dotenv;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
-extern crate diesel;
-
-mod chat;
-mod config;
-mod database;
-mod handlers;
-mod models;
-mod routes;
-mod schema;
-mod services;
+use webserver::chat::chat_server;
+use webserver::config::Config;
+use webserver::database::db;
+use webserver::database::db::MIGRATIONS;
+use webserver::routes;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
     dotenv().ok();
 
-    let database_url = Config::from_env().database_url;
-    let pool = db::establish_connection(&database_url);
+    let config = Config::from_env();
+    let pool = db::establish_connection(&config.database_url);
     let app_state = Arc::new(AtomicUsize::new(0));
 
     db::run_migrations(
@@ -43,6 +34,7 @@ async fn main() -> std::io::Result<()> {
 
         App::new()
             .app_data(Data::new(pool.clone()))
+            .app_data(Data::new(config.clone()))
             .configure(routes::init)
             .wrap(cors)
             .app_data(web::Data::from(app_state.clone()))
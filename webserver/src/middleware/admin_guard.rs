@@ -0,0 +1,24 @@
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpRequest};
+use futures_util::future::{ready, Ready};
+
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::role::Role;
+
+/// Route extractor that only resolves for users with the `Admin` role, rejecting with 403 otherwise.
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl FromRequest for AdminUser {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let user = req.extensions().get::<AuthenticatedUser>().copied();
+
+        ready(match user {
+            Some(user) if user.role == Role::Admin => Ok(AdminUser(user)),
+            Some(_) => Err(actix_web::error::ErrorForbidden("admin role required")),
+            None => Err(actix_web::error::ErrorUnauthorized("missing authentication")),
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use actix_web::dev::{Payload, ServiceRequest};
+use actix_web::web::Data;
+use actix_web::{Error, FromRequest, HttpRequest};
+use actix_web_httpauth::extractors::bearer::{self, BearerAuth};
+use actix_web_httpauth::extractors::AuthenticationError;
+use futures_util::future::{ready, Ready};
+
+use crate::config::Config;
+use crate::models::role::Role;
+use crate::services::auth_service;
+
+/// The authenticated user's id and role, injected into request extensions by [`validator`].
+#[derive(Debug, Clone, Copy)]
+pub struct AuthenticatedUser {
+    pub id: i32,
+    pub role: Role,
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AuthenticatedUser>()
+                .copied()
+                .ok_or_else(|| actix_web::error::ErrorUnauthorized("missing authentication")),
+        )
+    }
+}
+
+/// Validator passed to `HttpAuthentication::bearer`; rejects missing or invalid tokens with 401.
+pub async fn validator(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    let config = req.app_data::<Data<Config>>().cloned();
+
+    let config = match config {
+        Some(config) => config,
+        None => {
+            let challenge = bearer::Bearer::build().error(bearer::Error::InvalidRequest).finish();
+            return Err((AuthenticationError::new(challenge).into(), req));
+        }
+    };
+
+    match auth_service::verify_token(credentials.token(), &config.secret) {
+        Ok(claims) => {
+            req.extensions_mut().insert(AuthenticatedUser {
+                id: claims.sub,
+                role: claims.role,
+            });
+            Ok(req)
+        }
+        Err(_) => {
+            let challenge = bearer::Bearer::build().error(bearer::Error::InvalidToken).finish();
+            Err((AuthenticationError::new(challenge).into(), req))
+        }
+    }
+}
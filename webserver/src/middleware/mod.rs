@@ -0,0 +1,2 @@
+pub mod admin_guard;
+pub mod auth;
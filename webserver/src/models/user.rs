@@ -0,0 +1,32 @@
+use crate::models::role::Role;
+use crate::schema::users;
+use diesel::prelude::*;
+use serde::Serialize;
+
+#[derive(Queryable, Selectable, Identifiable, Serialize, Debug, Clone)]
+#[diesel(table_name = users)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct User {
+    pub id: i32,
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub role: Role,
+    pub email: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = users)]
+pub struct NewUser<'a> {
+    pub username: &'a str,
+    pub password_hash: &'a str,
+    pub role: Role,
+    pub email: Option<&'a str>,
+}
+
+/// Response payload returned on successful login: the user plus their signed JWT.
+#[derive(Serialize)]
+pub struct UserWithToken {
+    pub user: User,
+    pub jwt: String,
+}
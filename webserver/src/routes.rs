@@ -0,0 +1,27 @@
+use actix_web::{web, HttpResponse};
+use actix_web_httpauth::middleware::HttpAuthentication;
+
+use crate::handlers::user_handler;
+use crate::middleware::auth::{validator, AuthenticatedUser};
+
+pub fn init(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/register").route(web::post().to(user_handler::register)))
+        .service(web::resource("/login").route(web::post().to(user_handler::login)))
+        .service(
+            web::scope("")
+                .wrap(HttpAuthentication::bearer(validator))
+                .service(web::resource("/me").route(web::get().to(me)))
+                .service(
+                    web::resource("/admin/users").route(web::get().to(user_handler::list_users)),
+                )
+                .service(
+                    web::resource("/users/change_password")
+                        .route(web::post().to(user_handler::change_password)),
+                ),
+        );
+}
+
+async fn me(req: actix_web::HttpRequest) -> HttpResponse {
+    let user = req.extensions().get::<AuthenticatedUser>().copied();
+    HttpResponse::Ok().json(user.map(|user| user.id))
+}
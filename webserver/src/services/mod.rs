@@ -0,0 +1,3 @@
+pub mod auth_service;
+pub mod password_service;
+pub mod user_service;
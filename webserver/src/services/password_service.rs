@@ -0,0 +1,44 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::rngs::OsRng;
+
+/// Which algorithm `hash_password` should use. Selected via [`crate::config::Config`]
+/// so the work factor/backend is tunable per deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashBackend {
+    Bcrypt,
+    Argon2,
+}
+
+#[derive(Debug)]
+pub struct PasswordHashError;
+
+pub fn hash_password(plain: &str, backend: HashBackend, bcrypt_cost: u32) -> Result<String, PasswordHashError> {
+    match backend {
+        HashBackend::Bcrypt => bcrypt::hash(plain, bcrypt_cost).map_err(|_| PasswordHashError),
+        HashBackend::Argon2 => {
+            let salt = SaltString::generate(&mut OsRng);
+            Argon2::default()
+                .hash_password(plain.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|_| PasswordHashError)
+        }
+    }
+}
+
+/// Verifies `plain` against `hash`, auto-detecting whether `hash` is an Argon2 (`$argon2...`)
+/// or legacy bcrypt (`$2a$`/`$2b$`/`$2y$`) encoded hash.
+pub fn verify_password(hash: &str, plain: &str) -> Result<bool, PasswordHashError> {
+    if is_legacy_bcrypt(hash) {
+        bcrypt::verify(plain, hash).map_err(|_| PasswordHashError)
+    } else {
+        let parsed_hash = PasswordHash::new(hash).map_err(|_| PasswordHashError)?;
+        Ok(Argon2::default()
+            .verify_password(plain.as_bytes(), &parsed_hash)
+            .is_ok())
+    }
+}
+
+pub fn is_legacy_bcrypt(hash: &str) -> bool {
+    hash.starts_with("$2")
+}
@@ -1,52 +1,114 @@
-use crate::models::user::{NewUser, User};
+use crate::errors::ServiceError;
+use crate::models::role::Role;
+use crate::models::user::{NewUser, User, UserWithToken};
 use crate::schema::users;
+use crate::services::auth_service;
+use crate::services::password_service::{self, HashBackend};
 use diesel::prelude::*;
-use diesel::result::Error;
 
 pub async fn register_user(
     conn: &mut PgConnection,
     username: &str,
     password: &str,
-) -> Result<User, Error> {
-    let password_hash = &hash_password(password).expect("Failed to hash password");
+    email: Option<&str>,
+    hash_backend: HashBackend,
+    hash_cost: u32,
+) -> Result<User, ServiceError> {
+    let password_hash = &password_service::hash_password(password, hash_backend, hash_cost)
+        .map_err(|_| ServiceError::PasswordHashError)?;
     let new_user = NewUser {
         username,
         password_hash,
+        role: Role::User,
+        email,
     };
 
     let user = diesel::insert_into(users::table)
         .values(&new_user)
         .returning(User::as_returning())
-        .get_result(conn);
+        .get_result(conn)
+        .map_err(ServiceError::from);
     log::info!("{:?}", user);
-    return user;
+    user
 }
 
-pub async fn login(conn: &mut PgConnection, username: &str, password: &str) -> Result<User, Error> {
+pub async fn login(
+    conn: &mut PgConnection,
+    username_or_email: &str,
+    password: &str,
+    jwt_secret: &str,
+    jwt_ttl_seconds: i64,
+    hash_backend: HashBackend,
+    hash_cost: u32,
+) -> Result<UserWithToken, ServiceError> {
     let user = users::table
-        .filter(users::username.eq(username))
-        .first::<User>(conn);
+        .filter(
+            users::username
+                .eq(username_or_email)
+                .or(users::email.eq(username_or_email)),
+        )
+        .first::<User>(conn)
+        .map_err(|error| match error {
+            diesel::result::Error::NotFound => ServiceError::InvalidCredentials,
+            other => ServiceError::from(other),
+        })?;
 
-    match user {
-        Ok(user) => {
-            let is_password_correct = verify_password(&user.password_hash, password)
-                .expect("Password verification failed");
-            if is_password_correct {
-                return Ok(user);
-            } else {
-                return Err(Error::NotFound);
-            }
-        }
-        Err(error) => Err(error),
+    let is_password_correct = password_service::verify_password(&user.password_hash, password)
+        .map_err(|_| ServiceError::PasswordHashError)?;
+    if !is_password_correct {
+        return Err(ServiceError::InvalidCredentials);
     }
+
+    if password_service::is_legacy_bcrypt(&user.password_hash) {
+        let rehashed = password_service::hash_password(password, hash_backend, hash_cost)
+            .map_err(|_| ServiceError::PasswordHashError)?;
+        diesel::update(users::table.find(user.id))
+            .set(users::password_hash.eq(rehashed))
+            .execute(conn)
+            .map_err(ServiceError::from)?;
+    }
+
+    let jwt = auth_service::issue_token(user.id, user.role, jwt_secret, jwt_ttl_seconds)
+        .map_err(|_| ServiceError::TokenError)?;
+    Ok(UserWithToken { user, jwt })
 }
 
-fn hash_password(plain: &str) -> Result<String, bcrypt::BcryptError> {
-    bcrypt::hash(plain, bcrypt::DEFAULT_COST)
+pub async fn list_users(conn: &mut PgConnection) -> Result<Vec<User>, ServiceError> {
+    users::table
+        .select(User::as_select())
+        .load(conn)
+        .map_err(ServiceError::from)
 }
 
-fn verify_password(hash: &str, plain: &str) -> Result<bool, bcrypt::BcryptError> {
-    bcrypt::verify(plain, hash)
+pub async fn change_password(
+    conn: &mut PgConnection,
+    user_id: i32,
+    current_password: &str,
+    new_password: &str,
+    hash_backend: HashBackend,
+    hash_cost: u32,
+) -> Result<(), ServiceError> {
+    let user = users::table
+        .find(user_id)
+        .first::<User>(conn)
+        .map_err(ServiceError::from)?;
+
+    let is_current_password_correct =
+        password_service::verify_password(&user.password_hash, current_password)
+            .map_err(|_| ServiceError::PasswordHashError)?;
+    if !is_current_password_correct {
+        return Err(ServiceError::InvalidCredentials);
+    }
+
+    let new_password_hash = password_service::hash_password(new_password, hash_backend, hash_cost)
+        .map_err(|_| ServiceError::PasswordHashError)?;
+
+    diesel::update(users::table.find(user_id))
+        .set(users::password_hash.eq(new_password_hash))
+        .execute(conn)
+        .map_err(ServiceError::from)?;
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -63,7 +125,15 @@ mod tests {
         let username = "testuser";
         let password = "password123";
 
-        let result = register_user(&mut conn, username, password).await;
+        let result = register_user(
+            &mut conn,
+            username,
+            password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
         println!("{:?}", result);
         assert!(
             result.is_ok(),
@@ -74,8 +144,9 @@ mod tests {
         assert_eq!(registered_user.username, username);
 
         // Ensure the password is hashed
-        let is_password_correct = verify_password(&registered_user.password_hash, password)
-            .expect("Password verification failed");
+        let is_password_correct =
+            password_service::verify_password(&registered_user.password_hash, password)
+                .expect("Password verification failed");
         assert!(
             is_password_correct,
             "Password hashing or verification failed"
@@ -92,11 +163,27 @@ mod tests {
         let password = "password123";
 
         // First registration should succeed
-        let first_result = register_user(&mut conn, username, password).await;
+        let first_result = register_user(
+            &mut conn,
+            username,
+            password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
         assert!(first_result.is_ok(), "First user registration failed");
 
         // Second registration with the same username should fail
-        let second_result = register_user(&mut conn, username, password).await;
+        let second_result = register_user(
+            &mut conn,
+            username,
+            password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
         assert!(
             second_result.is_err(),
             "Second user registration succeeded when it should have failed due to duplicate username"
@@ -111,7 +198,15 @@ mod tests {
         let username = "testuser";
         let password = "password123";
 
-        let result = register_user(&mut db.conn(), username, password).await;
+        let result = register_user(
+            &mut db.conn(),
+            username,
+            password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
         println!("RESULT: {:?}", result);
         assert!(result.is_ok(), "User registration failed");
 
@@ -124,8 +219,257 @@ mod tests {
         );
 
         // Verify that the hashed password matches the original password
-        let is_password_correct = verify_password(&registered_user.password_hash, password)
-            .expect("Password verification failed");
+        let is_password_correct =
+            password_service::verify_password(&registered_user.password_hash, password)
+                .expect("Password verification failed");
         assert!(is_password_correct, "Password verification failed");
     }
+
+    #[actix_rt::test]
+    async fn test_login_issues_jwt() {
+        let db = TestDb::new();
+        test_db::run_migrations(&mut db.conn());
+
+        let username = "testuser";
+        let password = "password123";
+
+        register_user(
+            &mut db.conn(),
+            username,
+            password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("User registration failed");
+
+        let result = login(
+            &mut db.conn(),
+            username,
+            password,
+            "test-secret",
+            3600,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
+        assert!(result.is_ok(), "Login failed when it should have succeeded");
+
+        let user_with_token = result.unwrap();
+        assert_eq!(user_with_token.user.username, username);
+        assert!(
+            !user_with_token.jwt.is_empty(),
+            "Login did not produce a JWT"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_login_by_email_success() {
+        let db = TestDb::new();
+        test_db::run_migrations(&mut db.conn());
+
+        let username = "testuser";
+        let email = "testuser@example.com";
+        let password = "password123";
+
+        register_user(
+            &mut db.conn(),
+            username,
+            password,
+            Some(email),
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("User registration failed");
+
+        let result = login(
+            &mut db.conn(),
+            email,
+            password,
+            "test-secret",
+            3600,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
+        assert!(
+            result.is_ok(),
+            "Login by email failed when it should have succeeded"
+        );
+        assert_eq!(result.unwrap().user.username, username);
+    }
+
+    #[actix_rt::test]
+    async fn test_register_user_duplicate_email() {
+        let db = TestDb::new();
+        test_db::run_migrations(&mut db.conn());
+
+        let email = "duplicate@example.com";
+        let password = "password123";
+
+        register_user(
+            &mut db.conn(),
+            "first_user",
+            password,
+            Some(email),
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("First user registration failed");
+
+        let result = register_user(
+            &mut db.conn(),
+            "second_user",
+            password,
+            Some(email),
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
+
+        assert!(
+            matches!(result, Err(ServiceError::EmailTaken)),
+            "Expected ServiceError::EmailTaken for a duplicate email, got {:?}",
+            result
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_login_rehashes_legacy_bcrypt_to_configured_backend() {
+        let db = TestDb::new();
+        test_db::run_migrations(&mut db.conn());
+
+        let username = "testuser";
+        let password = "password123";
+
+        register_user(
+            &mut db.conn(),
+            username,
+            password,
+            None,
+            HashBackend::Bcrypt,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("User registration failed");
+
+        login(
+            &mut db.conn(),
+            username,
+            password,
+            "test-secret",
+            3600,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("Login should succeed against the legacy bcrypt hash");
+
+        let stored_user = users::table
+            .filter(users::username.eq(username))
+            .first::<User>(&mut db.conn())
+            .expect("Failed to load user");
+        assert!(
+            !password_service::is_legacy_bcrypt(&stored_user.password_hash),
+            "Stored hash was not upgraded from bcrypt"
+        );
+        assert!(password_service::verify_password(&stored_user.password_hash, password)
+            .expect("Password verification failed"));
+    }
+
+    #[actix_rt::test]
+    async fn test_change_password_success() {
+        let db = TestDb::new();
+        test_db::run_migrations(&mut db.conn());
+
+        let username = "testuser";
+        let current_password = "password123";
+        let new_password = "new_password456";
+
+        let user = register_user(
+            &mut db.conn(),
+            username,
+            current_password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("User registration failed");
+
+        change_password(
+            &mut db.conn(),
+            user.id,
+            current_password,
+            new_password,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("Password change failed when it should have succeeded");
+
+        let stored_user = users::table
+            .find(user.id)
+            .first::<User>(&mut db.conn())
+            .expect("Failed to load user");
+
+        // The stored hash actually changed...
+        assert_ne!(stored_user.password_hash, user.password_hash);
+
+        // ...the new password verifies...
+        assert!(
+            password_service::verify_password(&stored_user.password_hash, new_password)
+                .expect("Password verification failed")
+        );
+
+        // ...and the old password no longer verifies.
+        assert!(
+            !password_service::verify_password(&stored_user.password_hash, current_password)
+                .expect("Password verification failed")
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_change_password_wrong_current_password() {
+        let db = TestDb::new();
+        test_db::run_migrations(&mut db.conn());
+
+        let username = "testuser";
+        let current_password = "password123";
+
+        let user = register_user(
+            &mut db.conn(),
+            username,
+            current_password,
+            None,
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await
+        .expect("User registration failed");
+
+        let result = change_password(
+            &mut db.conn(),
+            user.id,
+            "wrong_password",
+            "new_password456",
+            HashBackend::Argon2,
+            bcrypt::DEFAULT_COST,
+        )
+        .await;
+
+        assert!(
+            result.is_err(),
+            "Password change succeeded when it should have failed due to wrong current password"
+        );
+
+        let stored_user = users::table
+            .find(user.id)
+            .first::<User>(&mut db.conn())
+            .expect("Failed to load user");
+        assert_eq!(stored_user.password_hash, user.password_hash);
+    }
 }
\ No newline at end of file
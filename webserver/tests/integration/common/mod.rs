@@ -0,0 +1,126 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::test;
+use actix_web::{web, App, Error};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use serde_json::Value;
+
+use webserver::config::Config;
+use webserver::models::role::Role;
+use webserver::schema::users;
+use webserver::services::password_service::HashBackend;
+
+pub fn test_config() -> Config {
+    Config {
+        database_url: String::new(),
+        secret: "integration-test-secret".to_string(),
+        jwt_ttl_seconds: 3600,
+        hash_backend: HashBackend::Argon2,
+        hash_cost: bcrypt::DEFAULT_COST,
+    }
+}
+
+/// Builds the full actix `App` (routes + pool) against the given test database pool,
+/// wrapped for use with `actix_web::test`.
+pub async fn get_app(
+    pool: Pool<ConnectionManager<PgConnection>>,
+) -> impl Service<actix_http::Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+    test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool))
+            .app_data(web::Data::new(test_config()))
+            .configure(webserver::routes::init),
+    )
+    .await
+}
+
+/// Registers a user and signs them in, returning the JWT issued at login.
+pub async fn register_and_signin<S, B>(app: &S, username: &str, password: &str) -> String
+where
+    S: Service<actix_http::Request, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let register_req = test::TestRequest::post()
+        .uri("/register")
+        .set_json(serde_json::json!({
+            "username": username,
+            "password": password,
+            "confirm_password": password,
+            "email": null,
+        }))
+        .to_request();
+    let register_resp = test::call_service(app, register_req).await;
+    assert!(
+        register_resp.status().is_success(),
+        "registration failed with status {}",
+        register_resp.status()
+    );
+
+    sign_in(app, username, password).await
+}
+
+/// Logs in an already-registered user, returning the JWT issued. Useful on its own
+/// when a test needs a fresh token after the user's role changed out of band.
+pub async fn sign_in<S, B>(app: &S, username: &str, password: &str) -> String
+where
+    S: Service<actix_http::Request, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let login_req = test::TestRequest::post()
+        .uri("/login")
+        .set_json(serde_json::json!({
+            "username_or_email": username,
+            "password": password,
+        }))
+        .to_request();
+    let login_body: Value = test::call_and_read_body_json(app, login_req).await;
+    login_body["jwt"]
+        .as_str()
+        .expect("login response did not include a jwt")
+        .to_string()
+}
+
+/// Promotes an already-registered user to `Role::Admin` directly in the database,
+/// since registration always creates regular users. Log in again afterwards to get
+/// a JWT whose `role` claim reflects the promotion.
+pub fn promote_to_admin(conn: &mut PgConnection, username: &str) {
+    diesel::update(users::table.filter(users::username.eq(username)))
+        .set(users::role.eq(Role::Admin))
+        .execute(conn)
+        .expect("Failed to promote test user to admin");
+}
+
+pub fn delete_user(conn: &mut PgConnection, username: &str) {
+    diesel::delete(users::table.filter(users::username.eq(username)))
+        .execute(conn)
+        .expect("Failed to delete test user");
+}
+
+/// Calls `req` against `app` and asserts it came back as an error response whose body
+/// contains `expected_message` — the rendered `Display` text of the `ServiceError`
+/// variant we expect (e.g. "username is already taken").
+pub async fn bad_request_test<S, B>(
+    app: &S,
+    req: actix_http::Request,
+    expected_message: &str,
+) where
+    S: Service<actix_http::Request, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let resp = test::call_service(app, req).await;
+    assert!(
+        !resp.status().is_success(),
+        "expected an error response, got {}",
+        resp.status()
+    );
+
+    let body = test::read_body(resp).await;
+    let body_str = String::from_utf8(body.to_vec()).expect("response body was not UTF-8");
+    assert!(
+        body_str.contains(expected_message),
+        "expected response body to contain {:?}, got: {}",
+        expected_message,
+        body_str
+    );
+}
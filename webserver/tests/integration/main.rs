@@ -0,0 +1,2 @@
+mod common;
+mod register_login_test;
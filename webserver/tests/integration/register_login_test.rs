@@ -0,0 +1,162 @@
+use actix_web::http::StatusCode;
+use actix_web::test;
+use webserver::database::test_db::TestDb;
+
+use crate::common::{
+    bad_request_test, delete_user, get_app, promote_to_admin, register_and_signin, sign_in,
+};
+
+#[actix_web::test]
+async fn test_register_login_and_access_protected_route() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let username = "integration_user";
+    let password = "password123";
+
+    let jwt = register_and_signin(&app, username, password).await;
+
+    let me_req = test::TestRequest::get()
+        .uri("/me")
+        .insert_header(("Authorization", format!("Bearer {}", jwt)))
+        .to_request();
+    let me_resp = test::call_service(&app, me_req).await;
+    assert!(
+        me_resp.status().is_success(),
+        "protected route rejected a valid token: {}",
+        me_resp.status()
+    );
+
+    delete_user(&mut db.conn(), username);
+}
+
+#[actix_web::test]
+async fn test_protected_route_rejects_missing_token() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let req = test::TestRequest::get().uri("/me").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_register_duplicate_username_is_rejected() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let username = "integration_duplicate";
+    let password = "password123";
+
+    register_and_signin(&app, username, password).await;
+
+    let duplicate_req = test::TestRequest::post()
+        .uri("/register")
+        .set_json(serde_json::json!({
+            "username": username,
+            "password": password,
+            "confirm_password": password,
+            "email": null,
+        }))
+        .to_request();
+    bad_request_test(&app, duplicate_req, "username is already taken").await;
+
+    delete_user(&mut db.conn(), username);
+}
+
+#[actix_web::test]
+async fn test_register_duplicate_email_is_rejected() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let email = "integration_duplicate@example.com";
+    let password = "password123";
+    let first_username = "integration_email_owner";
+    let second_username = "integration_email_claimer";
+
+    let register_req = test::TestRequest::post()
+        .uri("/register")
+        .set_json(serde_json::json!({
+            "username": first_username,
+            "password": password,
+            "confirm_password": password,
+            "email": email,
+        }))
+        .to_request();
+    let register_resp = test::call_service(&app, register_req).await;
+    assert!(
+        register_resp.status().is_success(),
+        "registration failed with status {}",
+        register_resp.status()
+    );
+
+    let duplicate_req = test::TestRequest::post()
+        .uri("/register")
+        .set_json(serde_json::json!({
+            "username": second_username,
+            "password": password,
+            "confirm_password": password,
+            "email": email,
+        }))
+        .to_request();
+    bad_request_test(&app, duplicate_req, "email is already registered").await;
+
+    delete_user(&mut db.conn(), first_username);
+    delete_user(&mut db.conn(), second_username);
+}
+
+#[actix_web::test]
+async fn test_admin_route_rejects_regular_user() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let username = "integration_non_admin";
+    let password = "password123";
+
+    let jwt = register_and_signin(&app, username, password).await;
+
+    let req = test::TestRequest::get()
+        .uri("/admin/users")
+        .insert_header(("Authorization", format!("Bearer {}", jwt)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    delete_user(&mut db.conn(), username);
+}
+
+#[actix_web::test]
+async fn test_admin_route_rejects_missing_token() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let req = test::TestRequest::get().uri("/admin/users").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn test_admin_route_allows_admin() {
+    let db = TestDb::new();
+    let app = get_app(db.pool()).await;
+
+    let username = "integration_admin";
+    let password = "password123";
+
+    register_and_signin(&app, username, password).await;
+    promote_to_admin(&mut db.conn(), username);
+    let jwt = sign_in(&app, username, password).await;
+
+    let req = test::TestRequest::get()
+        .uri("/admin/users")
+        .insert_header(("Authorization", format!("Bearer {}", jwt)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "admin was rejected from the admin route: {}",
+        resp.status()
+    );
+
+    delete_user(&mut db.conn(), username);
+}